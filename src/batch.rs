@@ -0,0 +1,293 @@
+//! Asynchronous batch translation via AWS Translate's
+//! `StartTextTranslationJob`, for catalogs large enough that real-time
+//! per-string `TranslateText` calls are impractical. Unlike the real-time
+//! path in `main.rs`, this submits every leaf string as one combined
+//! document and lets Translate produce one output file per target
+//! language, rather than making a `TranslateText` call per string -- but
+//! it still walks the locale tree leaf by leaf (protecting placeholders,
+//! reserializing with the original format) rather than shipping the raw
+//! structured file as an opaque blob of prose.
+
+use crate::{format, incremental, placeholder};
+use anyhow::{bail, Context, Result};
+use aws_sdk_s3 as s3;
+use aws_sdk_translate as translate;
+use log::{info, warn};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::Duration;
+
+/// Poll interval while waiting for a batch job to finish.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// S3 destination and custom-translation resources for a batch job,
+/// grouped so `run`'s signature doesn't grow with every new option.
+pub struct BatchOptions<'a> {
+    pub bucket: &'a str,
+    pub role_arn: &'a str,
+    pub terminology_name: Option<&'a str>,
+    pub parallel_data_name: Option<&'a str>,
+}
+
+/// Submit a batch translation job covering every target language at once,
+/// poll it to completion, and reassemble each language's output into the
+/// original locale file format alongside where the real-time path would
+/// have written it.
+pub async fn run(
+    s3_client: &s3::Client,
+    translate_client: &translate::Client,
+    input_file: &str,
+    source_language_code: &str,
+    target_language_codes: &[String],
+    format: &dyn format::Format,
+    options: &BatchOptions<'_>,
+) -> Result<()> {
+    let bucket = options.bucket;
+
+    let file_name = Path::new(input_file)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("could not derive a file name from the input path")?;
+
+    let input_prefix = "translate-batch/input";
+    let output_prefix = "translate-batch/output";
+
+    let original_content =
+        std::fs::read_to_string(input_file).with_context(|| format!("reading {input_file}"))?;
+    let json_value: Value = format.parse(&original_content)?;
+
+    // Protect every leaf string's placeholders the same way the real-time
+    // path does, and lay them out one per line so Translate sees plain
+    // prose rather than the locale file's own syntax (JSON punctuation,
+    // YAML indentation, etc.) as translatable text.
+    let mut leaves = Vec::new();
+    collect_protected_leaves(&json_value, &mut leaves);
+    let document = leaves
+        .iter()
+        .map(|(protected, _)| protected.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    s3_client
+        .put_object()
+        .bucket(bucket)
+        .key(format!("{input_prefix}/{file_name}.txt"))
+        .body(document.into_bytes().into())
+        .send()
+        .await
+        .context("uploading batch input to S3")?;
+
+    let mut request = translate_client
+        .start_text_translation_job()
+        .job_name(format!("translate-batch-{source_language_code}"))
+        .data_access_role_arn(options.role_arn)
+        .source_language_code(source_language_code)
+        .set_target_language_codes(Some(target_language_codes.to_vec()));
+
+    if let Some(name) = options.terminology_name {
+        request = request.terminology_names(name);
+    }
+
+    if let Some(name) = options.parallel_data_name {
+        request = request.parallel_data_names(name);
+    }
+
+    let job = request
+        .input_data_config(
+            translate::types::InputDataConfig::builder()
+                .s3_uri(format!("s3://{bucket}/{input_prefix}/"))
+                .content_type("text/plain")
+                .build()?,
+        )
+        .output_data_config(
+            translate::types::OutputDataConfig::builder()
+                .s3_uri(format!("s3://{bucket}/{output_prefix}/"))
+                .build()?,
+        )
+        .send()
+        .await
+        .context("starting batch translation job")?;
+
+    let job_id = job.job_id().context("batch job started without a job id")?.to_string();
+    info!("Started batch translation job {job_id}");
+
+    loop {
+        let describe = translate_client
+            .describe_text_translation_job()
+            .job_id(&job_id)
+            .send()
+            .await
+            .context("polling batch translation job")?;
+
+        let properties = describe
+            .text_translation_job_properties
+            .context("describe response had no job properties")?;
+
+        match properties.job_status {
+            Some(translate::types::JobStatus::Completed) => break,
+            Some(translate::types::JobStatus::Failed) => {
+                bail!(
+                    "batch translation job {job_id} failed: {}",
+                    properties.message.unwrap_or_default()
+                )
+            }
+            other => {
+                info!("batch translation job {job_id}: {other:?}");
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    // Translate lays output out as
+    // `{output_s3_uri}{account_id}-translate-{job_id}/{target_language_code}/{file_name}.{ext}`.
+    // We only know the job id ourselves, so list under it and match by
+    // target language code rather than reconstructing the account/job
+    // prefix exactly.
+    for target_language_code in target_language_codes {
+        let listing = s3_client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(format!("{output_prefix}/"))
+            .send()
+            .await
+            .context("listing batch output objects")?;
+
+        let key = listing
+            .contents()
+            .iter()
+            .filter_map(|obj| obj.key())
+            .find(|key| key.contains(&job_id) && key.contains(target_language_code.as_str()))
+            .with_context(|| format!("no batch output found for {target_language_code}"))?
+            .to_string();
+
+        let object = s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(&key)
+            .send()
+            .await
+            .with_context(|| format!("downloading batch output {key}"))?;
+
+        let data = object
+            .body
+            .collect()
+            .await
+            .with_context(|| format!("reading batch output {key}"))?
+            .into_bytes();
+
+        let translated_document = String::from_utf8(data.to_vec())
+            .with_context(|| format!("batch output {key} was not valid UTF-8"))?;
+        let translated_lines: Vec<&str> = translated_document.lines().collect();
+
+        if translated_lines.len() != leaves.len() {
+            warn!(
+                "{target_language_code}: batch output had {} lines but {} leaf strings were \
+                 submitted; falling back to the original text for any leaf without a matching line",
+                translated_lines.len(),
+                leaves.len()
+            );
+        }
+
+        let mut restored = VecDeque::with_capacity(leaves.len());
+        for (i, (protected, original)) in leaves.iter().enumerate() {
+            let translated_line = translated_lines.get(i).copied().unwrap_or(original.as_str());
+            restored.push_back(placeholder::restore(translated_line, protected, original));
+        }
+
+        let mut translated_value = json_value.clone();
+        replace_leaves(&mut translated_value, &mut restored);
+
+        let serialized = format
+            .serialize(&translated_value)
+            .with_context(|| format!("serializing batch output for {target_language_code}"))?;
+
+        let translated_path =
+            incremental::translated_path(target_language_code, format.extension());
+        std::fs::write(&translated_path, serialized)
+            .with_context(|| format!("writing {translated_path}"))?;
+
+        info!("{target_language_code}: wrote batch output to {translated_path}");
+    }
+
+    Ok(())
+}
+
+/// Walk `value` collecting each string leaf's placeholder-protected form
+/// alongside its original text, in a fixed traversal order that
+/// `replace_leaves` must mirror exactly.
+fn collect_protected_leaves(value: &Value, out: &mut Vec<(placeholder::Protected, String)>) {
+    match value {
+        Value::Object(obj) => {
+            for v in obj.values() {
+                collect_protected_leaves(v, out);
+            }
+        }
+        Value::String(s) => out.push((placeholder::protect(s), s.clone())),
+        _ => {}
+    }
+}
+
+/// Replace each string leaf of `value`, in the same traversal order as
+/// `collect_protected_leaves`, with the next entry popped from
+/// `replacements`.
+fn replace_leaves(value: &mut Value, replacements: &mut VecDeque<String>) {
+    match value {
+        Value::Object(obj) => {
+            for v in obj.values_mut() {
+                replace_leaves(v, replacements);
+            }
+        }
+        Value::String(s) => {
+            if let Some(replacement) = replacements.pop_front() {
+                *s = replacement;
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Map, Value};
+
+    #[test]
+    fn collects_and_replaces_leaves_in_the_same_order() {
+        let value = Value::Object(Map::from_iter([
+            ("a".to_string(), Value::String("hello {name}".to_string())),
+            (
+                "nested".to_string(),
+                Value::Object(Map::from_iter([(
+                    "b".to_string(),
+                    Value::String("bye".to_string()),
+                )])),
+            ),
+        ]));
+
+        let mut leaves = Vec::new();
+        collect_protected_leaves(&value, &mut leaves);
+        assert_eq!(leaves.len(), 2);
+        assert_eq!(leaves[0].1, "hello {name}");
+        assert_eq!(leaves[1].1, "bye");
+
+        let mut replacements: VecDeque<String> =
+            vec!["salut {name}".to_string(), "au revoir".to_string()].into();
+        let mut translated = value.clone();
+        replace_leaves(&mut translated, &mut replacements);
+
+        assert_eq!(
+            translated,
+            Value::Object(Map::from_iter([
+                ("a".to_string(), Value::String("salut {name}".to_string())),
+                (
+                    "nested".to_string(),
+                    Value::Object(Map::from_iter([(
+                        "b".to_string(),
+                        Value::String("au revoir".to_string()),
+                    )])),
+                ),
+            ]))
+        );
+    }
+}
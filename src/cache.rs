@@ -0,0 +1,24 @@
+//! Process-wide cache of already-translated strings, shared across all
+//! spawned per-language tasks so a repeated source string (brand names,
+//! "OK", "Cancel", ...) is served from memory once it has been translated
+//! for a target language. Lookups that miss while the same string is still
+//! being translated each call the Translate API.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub type TranslationCache = Arc<Mutex<HashMap<(String, String), String>>>;
+
+pub fn new_cache() -> TranslationCache {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+pub fn get(cache: &TranslationCache, target_language_code: &str, source_text: &str) -> Option<String> {
+    let key = (target_language_code.to_string(), source_text.to_string());
+    cache.lock().unwrap().get(&key).cloned()
+}
+
+pub fn insert(cache: &TranslationCache, target_language_code: &str, source_text: &str, translated_text: String) {
+    let key = (target_language_code.to_string(), source_text.to_string());
+    cache.lock().unwrap().insert(key, translated_text);
+}
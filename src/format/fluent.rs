@@ -0,0 +1,72 @@
+use super::Format;
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+/// Mozilla Fluent `.ftl` files. Only the flat `identifier = value` message
+/// form is supported; selectors and terms are out of scope for now.
+pub struct Fluent;
+
+impl Format for Fluent {
+    fn parse(&self, input: &str) -> Result<Value> {
+        let mut map = Map::new();
+        for line in input.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim();
+                if !key.is_empty() {
+                    map.insert(key.to_string(), Value::String(value.trim().to_string()));
+                }
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn serialize(&self, value: &Value) -> Result<String> {
+        let mut out = String::new();
+        if let Value::Object(obj) = value {
+            for (k, v) in obj {
+                if let Value::String(s) = v {
+                    out.push_str(&format!("{k} = {s}\n"));
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn extension(&self) -> &'static str {
+        "ftl"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_simple_messages() {
+        let value = Value::Object(Map::from_iter([(
+            "greeting".to_string(),
+            Value::String("Hello".to_string()),
+        )]));
+
+        let serialized = Fluent.serialize(&value).unwrap();
+        let reparsed = Fluent.parse(&serialized).unwrap();
+
+        assert_eq!(reparsed, value);
+    }
+
+    #[test]
+    fn ignores_comments_and_blank_lines() {
+        let parsed = Fluent.parse("# a comment\n\ngreeting = Hello\n").unwrap();
+        assert_eq!(
+            parsed,
+            Value::Object(Map::from_iter([(
+                "greeting".to_string(),
+                Value::String("Hello".to_string())
+            )]))
+        );
+    }
+}
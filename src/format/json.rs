@@ -0,0 +1,19 @@
+use super::Format;
+use anyhow::Result;
+use serde_json::{to_string_pretty, Value};
+
+pub struct Json;
+
+impl Format for Json {
+    fn parse(&self, input: &str) -> Result<Value> {
+        Ok(serde_json::from_str(input)?)
+    }
+
+    fn serialize(&self, value: &Value) -> Result<String> {
+        Ok(to_string_pretty(value)?)
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+}
@@ -0,0 +1,42 @@
+//! Pluggable locale file formats. Every format reads into (and writes back
+//! out of) the same `serde_json::Value` tree, so the recursive translation
+//! engine in `main.rs` stays format-agnostic and only the parsing/
+//! serializing at the edges changes.
+
+mod fluent;
+mod json;
+mod po;
+mod properties;
+mod yaml;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+pub trait Format: Send + Sync {
+    fn parse(&self, input: &str) -> Result<Value>;
+    fn serialize(&self, value: &Value) -> Result<String>;
+    /// File extension (without the dot) used for output files.
+    fn extension(&self) -> &'static str;
+}
+
+/// Resolve a `Format` by an explicit name, e.g. from `--format`.
+pub fn by_name(name: &str) -> Result<Box<dyn Format>> {
+    match name {
+        "json" => Ok(Box::new(json::Json)),
+        "yaml" | "yml" => Ok(Box::new(yaml::Yaml)),
+        "properties" => Ok(Box::new(properties::Properties)),
+        "po" => Ok(Box::new(po::Po)),
+        "ftl" | "fluent" => Ok(Box::new(fluent::Fluent)),
+        other => bail!("unknown locale file format '{other}'"),
+    }
+}
+
+/// Detect a `Format` from a file path's extension, defaulting to JSON if
+/// the path has no extension. An unrecognized extension is an error.
+pub fn for_path(path: &str) -> Result<Box<dyn Format>> {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("json");
+    by_name(ext)
+}
@@ -0,0 +1,161 @@
+use super::Format;
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+/// Gettext `.po` files. Each `msgid`/`msgstr` pair becomes a flat entry
+/// keyed by the (unquoted) `msgid`. Long strings wrapped across multiple
+/// quoted lines (gettext's standard line-continuation style) are
+/// concatenated back into one value.
+pub struct Po;
+
+impl Format for Po {
+    fn parse(&self, input: &str) -> Result<Value> {
+        let mut map = Map::new();
+        let mut current_id = String::new();
+        let mut current_str = String::new();
+        let mut target = Target::None;
+
+        for line in input.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("msgid ") {
+                flush(&mut map, &current_id, &current_str);
+                current_id = unquote(rest);
+                current_str.clear();
+                target = Target::Id;
+            } else if let Some(rest) = line.strip_prefix("msgstr ") {
+                current_str = unquote(rest);
+                target = Target::Str;
+            } else if line.starts_with('"') {
+                // Continuation line: a bare quoted fragment that extends
+                // whichever of msgid/msgstr was started most recently.
+                let fragment = unquote(line);
+                match target {
+                    Target::Id => current_id.push_str(&fragment),
+                    Target::Str => current_str.push_str(&fragment),
+                    Target::None => {}
+                }
+            } else if line.is_empty() {
+                flush(&mut map, &current_id, &current_str);
+                current_id.clear();
+                current_str.clear();
+                target = Target::None;
+            }
+        }
+        flush(&mut map, &current_id, &current_str);
+
+        Ok(Value::Object(map))
+    }
+
+    fn serialize(&self, value: &Value) -> Result<String> {
+        let mut out = String::new();
+        if let Value::Object(obj) = value {
+            for (k, v) in obj {
+                if let Value::String(s) = v {
+                    out.push_str(&format!(
+                        "msgid \"{}\"\nmsgstr \"{}\"\n\n",
+                        escape(k),
+                        escape(s)
+                    ));
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn extension(&self) -> &'static str {
+        "po"
+    }
+}
+
+enum Target {
+    None,
+    Id,
+    Str,
+}
+
+fn flush(map: &mut Map<String, Value>, id: &str, value: &str) {
+    if !id.is_empty() {
+        map.insert(id.to_string(), Value::String(value.to_string()));
+    }
+}
+
+/// Strip the surrounding quotes from a `msgid`/`msgstr`/continuation line
+/// and undo gettext's backslash escaping.
+fn unquote(s: &str) -> String {
+    let s = s.trim();
+    let inner = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s);
+    unescape(inner)
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Escape backslashes, quotes, and newlines so the value round-trips
+/// through `unescape` (and through real gettext tooling) unchanged.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_continuation_lines() {
+        let input = "msgid \"Line one \"\n\"line two\"\nmsgstr \"Ligne un \"\n\"ligne deux\"\n";
+        let parsed = Po.parse(input).unwrap();
+        assert_eq!(
+            parsed,
+            Value::Object(Map::from_iter([(
+                "Line one line two".to_string(),
+                Value::String("Ligne un ligne deux".to_string())
+            )]))
+        );
+    }
+
+    #[test]
+    fn round_trips_newlines_and_backslashes() {
+        let value = Value::Object(Map::from_iter([(
+            "a\\b".to_string(),
+            Value::String("line one\nline two".to_string()),
+        )]));
+
+        let serialized = Po.serialize(&value).unwrap();
+        let reparsed = Po.parse(&serialized).unwrap();
+
+        assert_eq!(reparsed, value);
+    }
+}
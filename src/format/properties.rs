@@ -0,0 +1,115 @@
+use super::Format;
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+/// Java `.properties` files: flat `key=value` lines, with nested locale
+/// keys flattened to dot-separated paths (`menu.file.open=Open`).
+pub struct Properties;
+
+impl Format for Properties {
+    fn parse(&self, input: &str) -> Result<Value> {
+        let mut map = Map::new();
+        for line in input.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                insert_dotted(&mut map, key.trim(), value.trim().to_string());
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn serialize(&self, value: &Value) -> Result<String> {
+        let mut lines = Vec::new();
+        flatten(value, String::new(), &mut lines);
+        lines.push(String::new());
+        Ok(lines.join("\n"))
+    }
+
+    fn extension(&self) -> &'static str {
+        "properties"
+    }
+}
+
+fn insert_dotted(map: &mut Map<String, Value>, key: &str, value: String) {
+    match key.split_once('.') {
+        Some((head, rest)) => {
+            let entry = map
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(child) = entry {
+                insert_dotted(child, rest, value);
+            }
+        }
+        None => {
+            map.insert(key.to_string(), Value::String(value));
+        }
+    }
+}
+
+fn flatten(value: &Value, prefix: String, lines: &mut Vec<String>) {
+    match value {
+        Value::Object(obj) => {
+            for (k, v) in obj {
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{prefix}.{k}")
+                };
+                flatten(v, key, lines);
+            }
+        }
+        Value::String(s) => lines.push(format!("{prefix}={s}")),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_dotted_keys() {
+        let value = Value::Object(Map::from_iter([(
+            "menu".to_string(),
+            Value::Object(Map::from_iter([("file".to_string(), Value::String("File".to_string()))])),
+        )]));
+
+        let serialized = Properties.serialize(&value).unwrap();
+        let reparsed = Properties.parse(&serialized).unwrap();
+
+        assert_eq!(reparsed, value);
+    }
+
+    #[test]
+    fn ignores_comments_and_blank_lines() {
+        let parsed = Properties.parse("# a comment\n\ngreeting=Hello\n").unwrap();
+        assert_eq!(
+            parsed,
+            Value::Object(Map::from_iter([(
+                "greeting".to_string(),
+                Value::String("Hello".to_string())
+            )]))
+        );
+    }
+
+    #[test]
+    fn dotted_key_colliding_with_existing_string_entry_is_dropped() {
+        // `menu` is already a leaf string, so `menu.file`'s attempt to
+        // nest into it finds a non-Object entry and silently drops the
+        // value rather than overwriting or erroring.
+        let mut map = Map::new();
+        insert_dotted(&mut map, "menu", "Menu".to_string());
+        insert_dotted(&mut map, "menu.file", "Open".to_string());
+
+        assert_eq!(
+            Value::Object(map),
+            Value::Object(Map::from_iter([(
+                "menu".to_string(),
+                Value::String("Menu".to_string())
+            )]))
+        );
+    }
+}
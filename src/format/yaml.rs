@@ -0,0 +1,51 @@
+use super::Format;
+use anyhow::Result;
+use serde_json::Value;
+
+pub struct Yaml;
+
+impl Format for Yaml {
+    fn parse(&self, input: &str) -> Result<Value> {
+        let yaml_value: serde_yaml::Value = serde_yaml::from_str(input)?;
+        Ok(serde_json::to_value(yaml_value)?)
+    }
+
+    fn serialize(&self, value: &Value) -> Result<String> {
+        Ok(serde_yaml::to_string(value)?)
+    }
+
+    fn extension(&self) -> &'static str {
+        "yaml"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Map, Value};
+
+    #[test]
+    fn round_trips_nested_strings() {
+        let value = Value::Object(Map::from_iter([(
+            "menu".to_string(),
+            Value::Object(Map::from_iter([("file".to_string(), Value::String("File".to_string()))])),
+        )]));
+
+        let serialized = Yaml.serialize(&value).unwrap();
+        let reparsed = Yaml.parse(&serialized).unwrap();
+
+        assert_eq!(reparsed, value);
+    }
+
+    #[test]
+    fn parses_flat_mapping() {
+        let parsed = Yaml.parse("greeting: Hello\nfarewell: Bye\n").unwrap();
+        assert_eq!(
+            parsed,
+            Value::Object(Map::from_iter([
+                ("greeting".to_string(), Value::String("Hello".to_string())),
+                ("farewell".to_string(), Value::String("Bye".to_string())),
+            ]))
+        );
+    }
+}
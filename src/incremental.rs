@@ -0,0 +1,94 @@
+//! Incremental translation support: skip re-translating strings whose
+//! source text hasn't changed since the last run, by comparing against a
+//! source-hash sidecar stored alongside each translated file.
+
+use serde_json::Value;
+use std::io::Read;
+
+/// Counts of what happened to the leaf strings in a translation run, used
+/// for the per-language summary log.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Counts {
+    pub added: usize,
+    pub changed: usize,
+    pub reused: usize,
+}
+
+impl Counts {
+    pub fn merge(&mut self, other: Counts) {
+        self.added += other.added;
+        self.changed += other.changed;
+        self.reused += other.reused;
+    }
+}
+
+/// Hash a source string for change detection. Not cryptographic; only
+/// needs to be stable and collision-resistant enough to tell "same text"
+/// from "different text" between runs. Uses a hand-rolled FNV-1a rather
+/// than `DefaultHasher`, whose algorithm the standard library explicitly
+/// does not guarantee to be stable across Rust/std releases -- a
+/// persisted sidecar hash can't afford to flip on every toolchain
+/// upgrade.
+pub fn hash_source(text: &str) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in text.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// Path to the translated output file for `target_language_code`, in the
+/// given locale file format's extension.
+pub fn translated_path(target_language_code: &str, extension: &str) -> String {
+    format!("assets/translated/{}.{}", target_language_code, extension)
+}
+
+/// Path to the source-hash sidecar file for `target_language_code`. Always
+/// JSON, regardless of the locale file format in use, since it's internal
+/// bookkeeping rather than a translatable asset.
+pub fn sources_path(target_language_code: &str) -> String {
+    format!("assets/translated/.sources/{}.json", target_language_code)
+}
+
+/// Load an existing translated locale file, if present, using `format` to
+/// parse it into the common value tree.
+pub fn load_existing(path: &str, format: &dyn crate::format::Format) -> Option<Value> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    format.parse(&content).ok()
+}
+
+/// Load an existing source-hash sidecar file, if present. Always JSON.
+pub fn load_existing_sources(path: &str) -> Option<Value> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_deterministic() {
+        assert_eq!(hash_source("hello"), hash_source("hello"));
+    }
+
+    #[test]
+    fn differs_for_different_input() {
+        assert_ne!(hash_source("hello"), hash_source("world"));
+    }
+
+    #[test]
+    fn matches_known_fnv1a_vector() {
+        // Standard FNV-1a test vector for the empty string.
+        assert_eq!(hash_source(""), "cbf29ce484222325");
+    }
+}
@@ -1,14 +1,23 @@
-use anyhow::Result;
+mod batch;
+mod cache;
+mod format;
+mod incremental;
+mod placeholder;
+mod terminology;
+mod verify;
+
+use anyhow::{bail, Result};
 use async_recursion::async_recursion;
 use aws_config::Region;
 use aws_sdk_translate as translate;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use env_logger::Env;
 use log::{debug, info};
 use serde_json::{to_string_pretty, Map, Value};
 use std::{
     fs::{File, OpenOptions},
     io::{Read, Write},
+    sync::Arc,
     time::Duration,
 };
 
@@ -30,6 +39,106 @@ struct Args {
     /// Source language code
     #[arg(long, default_value = "en")]
     source_language_code: String,
+
+    /// Retranslate every string, ignoring any existing translated output
+    /// and source-hash sidecar
+    #[arg(long, default_value_t = false)]
+    force: bool,
+
+    /// Disable the in-memory dedup cache for repeated strings
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+
+    /// Path to a custom terminology CSV or TMX file to import and apply to
+    /// every translation
+    #[arg(long)]
+    terminology: Option<String>,
+
+    /// S3 URI of a TMX parallel-data file to import for Active Custom
+    /// Translation (applies to batch jobs only)
+    #[arg(long)]
+    parallel_data: Option<String>,
+
+    /// Formality level to request (only honoured for languages that
+    /// support it, e.g. German, Spanish, Japanese)
+    #[arg(long, value_enum)]
+    formality: Option<Formality>,
+
+    /// Mask profane words and phrases in the translated output
+    #[arg(long, default_value_t = false)]
+    mask_profanity: bool,
+
+    /// Locale file format to use (json, yaml, properties, po, ftl).
+    /// Defaults to detecting from the input file's extension
+    #[arg(long)]
+    format: Option<String>,
+
+    /// Maximum number of in-flight TranslateText calls across all
+    /// languages at once, to stay under the service's throttling limits
+    #[arg(long, default_value_t = 10)]
+    max_concurrency: usize,
+
+    /// Translate via an asynchronous StartTextTranslationJob batch job
+    /// instead of per-string TranslateText calls, for catalogs too large
+    /// to translate one string at a time
+    #[arg(long, default_value_t = false)]
+    batch: bool,
+
+    /// S3 bucket used to stage batch job input/output (required with
+    /// --batch)
+    #[arg(long)]
+    batch_s3_bucket: Option<String>,
+
+    /// IAM role ARN that AWS Translate assumes to read/write the batch
+    /// S3 bucket (required with --batch)
+    #[arg(long)]
+    batch_role_arn: Option<String>,
+
+    /// After translating, back-translate the result to the source
+    /// language and write a quality report of entries that may have
+    /// drifted in meaning to assets/reports/{lang}.json
+    #[arg(long, default_value_t = false)]
+    verify: bool,
+
+    /// Similarity score (0.0-1.0) below which a back-translated entry is
+    /// flagged in the --verify report
+    #[arg(long, default_value_t = 0.8)]
+    verify_threshold: f64,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Formality {
+    Formal,
+    Informal,
+}
+
+impl From<Formality> for translate::types::Formality {
+    fn from(formality: Formality) -> Self {
+        match formality {
+            Formality::Formal => translate::types::Formality::Formal,
+            Formality::Informal => translate::types::Formality::Informal,
+        }
+    }
+}
+
+/// Translation behavior shared by every leaf in the tree, grouped so the
+/// recursive walk's signature doesn't grow with every new flag.
+struct TranslateOptions {
+    cache: Option<cache::TranslationCache>,
+    terminology_name: Option<String>,
+    formality: Option<Formality>,
+    mask_profanity: bool,
+    semaphore: Arc<tokio::sync::Semaphore>,
+}
+
+/// Everything `create_translation_file` needs beyond the language pair and
+/// the AWS client, grouped for the same reason as `TranslateOptions`.
+struct RunOptions {
+    force: bool,
+    format: Arc<dyn format::Format>,
+    /// `Some(threshold)` enables the `--verify` back-translation report.
+    verify_threshold: Option<f64>,
+    translate: TranslateOptions,
 }
 
 #[::tokio::main]
@@ -57,11 +166,73 @@ async fn main() -> Result<()> {
 
     let client = translate::Client::new(&config);
 
+    let terminology_name = match &args.terminology {
+        Some(path) => Some(terminology::import_terminology(&client, path).await?),
+        None => None,
+    };
+
+    let parallel_data_name = match &args.parallel_data {
+        Some(path) => Some(terminology::import_parallel_data(&client, path).await?),
+        None => None,
+    };
+
     let language_codes = match client.list_languages().send().await {
         Ok(resp) => resp.languages.unwrap_or_default(),
         Err(err) => return Err(err.into()), // Error is now properly handled
     };
 
+    let translation_cache = if args.no_cache {
+        None
+    } else {
+        Some(cache::new_cache())
+    };
+
+    let format: Arc<dyn format::Format> = match &args.format {
+        Some(name) => Arc::from(format::by_name(name)?),
+        None => Arc::from(format::for_path(&args.input_file)?),
+    };
+
+    if args.batch {
+        let (Some(bucket), Some(role_arn)) =
+            (args.batch_s3_bucket.as_deref(), args.batch_role_arn.as_deref())
+        else {
+            bail!("--batch requires --batch-s3-bucket and --batch-role-arn");
+        };
+
+        let target_language_codes: Vec<String> = language_codes
+            .iter()
+            .map(|l| l.language_code().to_string())
+            .filter(|code| code != "auto" && code != &args.source_language_code)
+            .collect();
+
+        let s3_client = aws_sdk_s3::Client::new(&config);
+
+        let batch_options = batch::BatchOptions {
+            bucket,
+            role_arn,
+            terminology_name: terminology_name.as_deref(),
+            parallel_data_name: parallel_data_name.as_deref(),
+        };
+
+        batch::run(
+            &s3_client,
+            &client,
+            &args.input_file,
+            &args.source_language_code,
+            &target_language_codes,
+            format.as_ref(),
+            &batch_options,
+        )
+        .await?;
+
+        let duration = start.elapsed();
+        info!("Time elapsed: {:?}", duration);
+        info!("Completed batch translation for {} languages", target_language_codes.len());
+        return Ok(());
+    }
+
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(args.max_concurrency));
+
     let mut handles = Vec::new();
 
     for language_code in &language_codes {
@@ -83,6 +254,18 @@ async fn main() -> Result<()> {
         let language_code = language_code.clone(); // Clone the language code
         let input_file = args.input_file.clone();
         let client = client.clone();
+        let options = RunOptions {
+            force: args.force,
+            format: format.clone(),
+            verify_threshold: args.verify.then_some(args.verify_threshold),
+            translate: TranslateOptions {
+                cache: translation_cache.clone(),
+                terminology_name: terminology_name.clone(),
+                formality: args.formality,
+                mask_profanity: args.mask_profanity,
+                semaphore: semaphore.clone(),
+            },
+        };
 
         // Spawn a new asynchronous task for each language translation
         let original_language_code = args.source_language_code.clone();
@@ -90,7 +273,7 @@ async fn main() -> Result<()> {
         let target_language_code = language_code;
 
         let handle = tokio::spawn(async move {
-            create_translation_file(original_language_code.as_str(), target_language_code.language_code(), &mut original_file_content, client).await
+            create_translation_file(original_language_code.as_str(), target_language_code.language_code(), &mut original_file_content, client, options).await
         });
 
         // Store the task handle
@@ -115,24 +298,47 @@ async fn create_translation_file(
     target_language_code: &str,
     original_file_content: &mut File,
     translate_client: aws_sdk_translate::Client,
+    options: RunOptions,
 ) -> Result<()> {
     let mut original_content = String::new();
     original_file_content.read_to_string(&mut original_content)?;
 
-    // Parse the JSON content
-    let json_value: Value = serde_json::from_str(&original_content)?;
+    // Parse the locale file into the common value tree
+    let json_value: Value = options.format.parse(&original_content)?;
+
+    let translated_path =
+        incremental::translated_path(target_language_code, options.format.extension());
+    let sources_path = incremental::sources_path(target_language_code);
+
+    let (existing_translation, existing_sources) = if options.force {
+        (None, None)
+    } else {
+        (
+            incremental::load_existing(&translated_path, options.format.as_ref()),
+            incremental::load_existing_sources(&sources_path),
+        )
+    };
 
     // Implement retry logic
     let mut retries = 0;
     let max_retries = 5;
     let mut delay = Duration::from_secs(1); // Starting delay of 1 second
 
-    // Recursively translate the JSON object
-    let translated_json = loop {
-        match translate_json_object(original_language_code, target_language_code, json_value.clone(), &translate_client).await {
-            Ok(translated_json) => {
-                break to_string_pretty(&translated_json)?
-            },
+    // Recursively translate the JSON object, reusing prior output where the
+    // source text is unchanged
+    let (translated_json, source_hashes, counts) = loop {
+        match translate_json_object(
+            original_language_code,
+            target_language_code,
+            json_value.clone(),
+            existing_translation.as_ref(),
+            existing_sources.as_ref(),
+            &translate_client,
+            &options.translate,
+        )
+        .await
+        {
+            Ok(result) => break result,
             Err(_) if retries < max_retries => {
                 tokio::time::sleep(delay).await;
                 retries += 1;
@@ -143,57 +349,225 @@ async fn create_translation_file(
         }
     };
 
+    info!(
+        "{}: {} added, {} changed, {} reused",
+        target_language_code, counts.added, counts.changed, counts.reused
+    );
+
     // Write the translated JSON to a new file
+    std::fs::create_dir_all("assets/translated/.sources")?;
+
     let mut file = OpenOptions::new()
         .write(true)
         .create(true)
-        .open(format!("assets/translated/{}.json", target_language_code))?;
+        .truncate(true)
+        .open(&translated_path)?;
+    file.write_all(options.format.serialize(&translated_json)?.as_bytes())?;
+
+    let mut sources_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&sources_path)?;
+    sources_file.write_all(to_string_pretty(&source_hashes)?.as_bytes())?;
+
+    if let Some(threshold) = options.verify_threshold {
+        let findings = verify::check(
+            original_language_code,
+            target_language_code,
+            &json_value,
+            &translated_json,
+            &translate_client,
+            &options.translate.semaphore,
+            threshold,
+        )
+        .await?;
 
-    file.write_all(translated_json.as_bytes())?;
+        info!(
+            "{}: {} entries below similarity threshold {}",
+            target_language_code,
+            findings.len(),
+            threshold
+        );
+
+        std::fs::create_dir_all("assets/reports")?;
+        let report = Value::Array(findings.iter().map(verify::Finding::to_value).collect());
+        let mut report_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(format!("assets/reports/{target_language_code}.json"))?;
+        report_file.write_all(to_string_pretty(&report)?.as_bytes())?;
+    }
 
     Ok(())
 }
 
+/// Whether a `TranslateText` failure looks like the service rejecting the
+/// formality setting for this language pair, as opposed to throttling, a
+/// network error, or anything else that retrying without formality
+/// wouldn't fix (and whose message we'd otherwise mask by retrying).
+fn is_formality_rejection(
+    err: &aws_sdk_translate::error::SdkError<aws_sdk_translate::operation::translate_text::TranslateTextError>,
+) -> bool {
+    use aws_sdk_translate::operation::translate_text::TranslateTextError;
+
+    matches!(
+        err.as_service_error(),
+        Some(TranslateTextError::UnsupportedLanguagePairException(_))
+            | Some(TranslateTextError::InvalidRequestException(_))
+    )
+}
+
 #[async_recursion]
 async fn translate_json_object(
     source_language_code: &str,
     target_language: &str,
     json_value: Value,
+    existing_translation: Option<&Value>,
+    existing_sources: Option<&Value>,
     translate_client: &aws_sdk_translate::Client,
-) -> Result<Value, translate::Error> {
+    options: &TranslateOptions,
+) -> Result<(Value, Value, incremental::Counts), translate::Error> {
     match json_value {
         Value::Object(obj) => {
-            let mut new_obj = Map::new();
-            for (k, v) in obj {
-                match translate_json_object(
-                    source_language_code,
-                    target_language,
-                    v,
-                    translate_client,
-                )
-                .await
-                {
-                    Ok(translated_value) => {
-                        new_obj.insert(k, translated_value);
-                    }
-                    Err(err) => return Err(err),
+            // Translate sibling leaf strings concurrently rather than one
+            // at a time; the semaphore threaded through to each leaf still
+            // bounds total in-flight TranslateText calls across the whole
+            // tree (and across every language's tree at once).
+            let children = obj.into_iter().map(|(k, v)| {
+                let existing_child = existing_translation.and_then(|t| t.get(&k));
+                let existing_source_child = existing_sources.and_then(|t| t.get(&k));
+
+                async move {
+                    translate_json_object(
+                        source_language_code,
+                        target_language,
+                        v,
+                        existing_child,
+                        existing_source_child,
+                        translate_client,
+                        options,
+                    )
+                    .await
+                    .map(|result| (k, result))
                 }
+            });
+
+            let mut new_obj = Map::new();
+            let mut new_sources = Map::new();
+            let mut counts = incremental::Counts::default();
+
+            for (k, (translated_value, source_hashes, child_counts)) in
+                futures::future::try_join_all(children).await?
+            {
+                new_obj.insert(k.clone(), translated_value);
+                new_sources.insert(k, source_hashes);
+                counts.merge(child_counts);
             }
-            Ok(Value::Object(new_obj))
+
+            Ok((Value::Object(new_obj), Value::Object(new_sources), counts))
         }
         Value::String(s) => {
-            if s == "" { return Ok(Value::String("".to_string())); }
-            let translated_text = translate_client
-                .translate_text()
-                .source_language_code(source_language_code)
-                .target_language_code(target_language)
-                .text(&s)
-                .send()
-                .await?
-                .translated_text;
-
-            Ok(Value::String(translated_text))
+            if s == "" {
+                return Ok((
+                    Value::String("".to_string()),
+                    Value::String(incremental::hash_source("")),
+                    incremental::Counts::default(),
+                ));
+            }
+
+            let hash = incremental::hash_source(&s);
+            let existing_hash = existing_sources.and_then(|h| h.as_str());
+            let existing_value = existing_translation.and_then(|v| v.as_str());
+
+            if let (Some(existing_hash), Some(existing_value)) = (existing_hash, existing_value) {
+                if existing_hash == hash {
+                    return Ok((
+                        Value::String(existing_value.to_string()),
+                        Value::String(hash),
+                        incremental::Counts {
+                            reused: 1,
+                            ..Default::default()
+                        },
+                    ));
+                }
+            }
+
+            let restored = if let Some(cached) = options
+                .cache
+                .as_ref()
+                .and_then(|c| cache::get(c, target_language, &s))
+            {
+                cached
+            } else {
+                let _permit = options
+                    .semaphore
+                    .acquire()
+                    .await
+                    .expect("semaphore is never closed");
+
+                let protected = placeholder::protect(&s);
+                let build_request = |formality: Option<Formality>| {
+                    let mut request = translate_client
+                        .translate_text()
+                        .source_language_code(source_language_code)
+                        .target_language_code(target_language)
+                        .text(&protected.text);
+
+                    if let Some(name) = options.terminology_name.as_deref() {
+                        request = request.terminology_names(name);
+                    }
+
+                    if formality.is_some() || options.mask_profanity {
+                        let mut settings = translate::types::TranslationSettings::builder();
+                        if let Some(formality) = formality {
+                            settings = settings.formality(formality.into());
+                        }
+                        if options.mask_profanity {
+                            settings = settings.profanity(translate::types::Profanity::Mask);
+                        }
+                        request = request.settings(settings.build());
+                    }
+
+                    request
+                };
+
+                let response = match build_request(options.formality).send().await {
+                    Ok(response) => response,
+                    Err(err) if options.formality.is_some() && is_formality_rejection(&err) => {
+                        debug!(
+                            "formality setting rejected for {}, retrying without it: {}",
+                            target_language, err
+                        );
+                        build_request(None).send().await?
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+
+                let translated_text = response.translated_text;
+
+                let restored = placeholder::restore(&translated_text, &protected, &s);
+
+                if let Some(c) = options.cache.as_ref() {
+                    cache::insert(c, target_language, &s, restored.clone());
+                }
+
+                restored
+            };
+
+            let counts = if existing_hash.is_some() {
+                incremental::Counts { changed: 1, ..Default::default() }
+            } else {
+                incremental::Counts { added: 1, ..Default::default() }
+            };
+
+            Ok((Value::String(restored), Value::String(hash), counts))
         }
-        _ => Ok(json_value), // Non-string values are left as-is
+        other => Ok((
+            other,
+            Value::Null,
+            incremental::Counts::default(),
+        )), // Non-string values are left as-is and not tracked for changes
     }
 }
@@ -0,0 +1,134 @@
+//! Protects i18n interpolation placeholders (ICU, i18next, printf, positional)
+//! from being mangled or reordered by the translation engine.
+
+use log::warn;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// Private-use-area sentinel markers so the translation engine treats the
+// substituted token as opaque text rather than something worth "improving".
+const SENTINEL_START: char = '\u{E000}';
+const SENTINEL_END: char = '\u{E001}';
+
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(\{\{[^{}]+\}\})", // i18next {{count}}
+        r"|(\{[^{}]+\})",    // ICU / named {username}
+        r"|(%\d*\$?[sdif])", // printf %s, %d, %1$s
+        r"|(\$\d+)",         // positional $1
+    ))
+    .expect("placeholder regex is valid")
+});
+
+static SENTINEL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!("{SENTINEL_START}(\\d+){SENTINEL_END}")).expect("sentinel regex is valid")
+});
+
+/// A string with its interpolation placeholders swapped out for sentinel
+/// tokens, plus the mapping needed to restore them after translation.
+pub struct Protected {
+    pub text: String,
+    placeholders: Vec<String>,
+}
+
+/// Scan `text` for known placeholder patterns and replace each with a
+/// numbered sentinel token that survives a round-trip through the
+/// translation engine untouched.
+pub fn protect(text: &str) -> Protected {
+    let mut placeholders = Vec::new();
+    let protected = PLACEHOLDER_RE.replace_all(text, |caps: &regex::Captures| {
+        let matched = caps.get(0).unwrap().as_str().to_string();
+        let index = placeholders.len();
+        placeholders.push(matched);
+        format!("{SENTINEL_START}{index}{SENTINEL_END}")
+    });
+
+    Protected {
+        text: protected.into_owned(),
+        placeholders,
+    }
+}
+
+/// Reverse the sentinel substitution, restoring the original placeholder
+/// text. If the engine dropped or duplicated a sentinel, fall back to the
+/// original (pre-protection) string and log a warning rather than emitting
+/// a mangled translation.
+pub fn restore(translated: &str, protected: &Protected, original: &str) -> String {
+    if protected.placeholders.is_empty() {
+        return translated.to_string();
+    }
+
+    let found_indices: Vec<usize> = SENTINEL_RE
+        .captures_iter(translated)
+        .filter_map(|caps| caps[1].parse().ok())
+        .collect();
+
+    let mut seen = vec![false; protected.placeholders.len()];
+    let mut valid = found_indices.len() == protected.placeholders.len();
+    for index in &found_indices {
+        match seen.get_mut(*index) {
+            Some(slot) if !*slot => *slot = true,
+            _ => {
+                valid = false;
+                break;
+            }
+        }
+    }
+
+    if !valid {
+        warn!(
+            "placeholder sentinels don't match 1:1 ({} expected, indices {:?} found); falling back to original text",
+            protected.placeholders.len(),
+            found_indices
+        );
+        return original.to_string();
+    }
+
+    let restored = SENTINEL_RE.replace_all(translated, |caps: &regex::Captures| {
+        let index: usize = caps[1].parse().unwrap_or(usize::MAX);
+        protected
+            .placeholders
+            .get(index)
+            .cloned()
+            .unwrap_or_default()
+    });
+
+    restored.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_single_placeholder() {
+        let protected = protect("Hello, {name}!");
+        assert_eq!(protected.text, format!("Hello, {SENTINEL_START}0{SENTINEL_END}!"));
+
+        let restored = restore(&protected.text, &protected, "Hello, {name}!");
+        assert_eq!(restored, "Hello, {name}!");
+    }
+
+    #[test]
+    fn falls_back_when_a_sentinel_is_dropped() {
+        let protected = protect("{greeting}, {name}!");
+        // Engine dropped the second sentinel entirely.
+        let mangled = format!("{SENTINEL_START}0{SENTINEL_END}!");
+
+        let restored = restore(&mangled, &protected, "{greeting}, {name}!");
+        assert_eq!(restored, "{greeting}, {name}!");
+    }
+
+    #[test]
+    fn falls_back_when_a_sentinel_is_duplicated() {
+        let protected = protect("{greeting}, {name}!");
+        // Same total count as expected, but index 0 appears twice and
+        // index 1 never appears.
+        let mangled = format!(
+            "{SENTINEL_START}0{SENTINEL_END}, {SENTINEL_START}0{SENTINEL_END}!"
+        );
+
+        let restored = restore(&mangled, &protected, "{greeting}, {name}!");
+        assert_eq!(restored, "{greeting}, {name}!");
+    }
+}
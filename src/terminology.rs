@@ -0,0 +1,76 @@
+//! Imports a custom terminology (and, optionally, parallel data) resource
+//! once at startup so every `TranslateText` call can reference it, keeping
+//! domain-specific terms (product names, UI verbs) consistent across the
+//! whole locale tree.
+
+use anyhow::{Context, Result};
+use aws_sdk_translate::primitives::Blob;
+use aws_sdk_translate::types::{MergeStrategy, ParallelDataFormat, TerminologyDataFormat};
+use log::info;
+use std::path::Path;
+
+/// Derive a terminology/parallel-data resource name from the file stem,
+/// e.g. `terms.csv` -> `terms`.
+fn resource_name(path: &str) -> Result<String> {
+    Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_string())
+        .context("could not derive a resource name from path")
+}
+
+fn data_format_for(path: &str) -> TerminologyDataFormat {
+    match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("tmx") => TerminologyDataFormat::Tmx,
+        Some("tsv") => TerminologyDataFormat::Tsv,
+        _ => TerminologyDataFormat::Csv,
+    }
+}
+
+/// Import (or replace, via OVERWRITE) a custom terminology resource from a CSV
+/// or TMX file, returning the terminology name to pass into `TranslateText`.
+pub async fn import_terminology(client: &aws_sdk_translate::Client, path: &str) -> Result<String> {
+    let name = resource_name(path)?;
+    let data = std::fs::read(path).with_context(|| format!("reading terminology file {path}"))?;
+
+    client
+        .import_terminology()
+        .name(&name)
+        .merge_strategy(MergeStrategy::Overwrite)
+        .terminology_data(
+            aws_sdk_translate::types::TerminologyData::builder()
+                .file(Blob::new(data))
+                .format(data_format_for(path))
+                .build()?,
+        )
+        .send()
+        .await
+        .with_context(|| format!("importing terminology {name}"))?;
+
+    info!("Imported custom terminology '{}' from {}", name, path);
+    Ok(name)
+}
+
+/// Import a parallel-data resource for Active Custom Translation. This is
+/// only honoured by the asynchronous batch job path, since `TranslateText`
+/// itself has no parallel-data parameter. `path` must already be an
+/// `s3://` URI, since `CreateParallelData` reads its TMX data from S3.
+pub async fn import_parallel_data(client: &aws_sdk_translate::Client, path: &str) -> Result<String> {
+    let name = resource_name(path)?;
+
+    client
+        .create_parallel_data()
+        .name(&name)
+        .parallel_data_config(
+            aws_sdk_translate::types::ParallelDataConfig::builder()
+                .s3_uri(path)
+                .format(ParallelDataFormat::Tmx)
+                .build(),
+        )
+        .send()
+        .await
+        .with_context(|| format!("importing parallel data {name}"))?;
+
+    info!("Imported parallel data '{}' from {}", name, path);
+    Ok(name)
+}
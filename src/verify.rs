@@ -0,0 +1,186 @@
+//! Round-trip back-translation quality check: after translating a file,
+//! translate the result back to the source language and diff it against
+//! the original to surface entries whose meaning likely drifted, giving
+//! teams an offline review signal without any service beyond the
+//! Translate client already in use.
+
+use async_recursion::async_recursion;
+use aws_sdk_translate as translate;
+use serde_json::{Map, Value};
+
+/// A leaf whose back-translation similarity fell below `--verify-threshold`.
+#[derive(Debug)]
+pub struct Finding {
+    pub key: String,
+    pub original: String,
+    pub forward: String,
+    pub back_translated: String,
+    pub similarity: f64,
+}
+
+impl Finding {
+    /// Render as the JSON object written into `assets/reports/{lang}.json`.
+    pub fn to_value(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("key".to_string(), Value::String(self.key.clone()));
+        obj.insert("original".to_string(), Value::String(self.original.clone()));
+        obj.insert("forward".to_string(), Value::String(self.forward.clone()));
+        obj.insert(
+            "back_translated".to_string(),
+            Value::String(self.back_translated.clone()),
+        );
+        obj.insert(
+            "similarity".to_string(),
+            serde_json::Number::from_f64(self.similarity)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+        );
+        Value::Object(obj)
+    }
+}
+
+/// Recursively back-translate every leaf string in `translated` (keyed the
+/// same as `original`) from `target_language_code` back to
+/// `source_language_code`, scoring each against the original text, and
+/// return the leaves that fell below `threshold`.
+pub async fn check(
+    source_language_code: &str,
+    target_language_code: &str,
+    original: &Value,
+    translated: &Value,
+    translate_client: &translate::Client,
+    semaphore: &tokio::sync::Semaphore,
+    threshold: f64,
+) -> Result<Vec<Finding>, translate::Error> {
+    let ctx = WalkContext {
+        source_language_code,
+        target_language_code,
+        translate_client,
+        semaphore,
+        threshold,
+    };
+
+    let mut findings = Vec::new();
+    walk(&ctx, original, translated, String::new(), &mut findings).await?;
+    Ok(findings)
+}
+
+/// Back-translation settings shared by every node of the walk, grouped so
+/// `walk`'s signature doesn't grow with every new knob.
+struct WalkContext<'a> {
+    source_language_code: &'a str,
+    target_language_code: &'a str,
+    translate_client: &'a translate::Client,
+    semaphore: &'a tokio::sync::Semaphore,
+    threshold: f64,
+}
+
+#[async_recursion]
+async fn walk(
+    ctx: &WalkContext,
+    original: &Value,
+    translated: &Value,
+    path: String,
+    findings: &mut Vec<Finding>,
+) -> Result<(), translate::Error> {
+    match (original, translated) {
+        (Value::Object(orig_obj), Value::Object(trans_obj)) => {
+            for (k, orig_child) in orig_obj {
+                let Some(trans_child) = trans_obj.get(k) else {
+                    continue;
+                };
+                let child_path = if path.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{path}.{k}")
+                };
+
+                walk(ctx, orig_child, trans_child, child_path, findings).await?;
+            }
+        }
+        (Value::String(orig_s), Value::String(trans_s)) => {
+            if orig_s.is_empty() || trans_s.is_empty() {
+                return Ok(());
+            }
+
+            let _permit = ctx.semaphore.acquire().await.expect("semaphore is never closed");
+
+            let response = ctx
+                .translate_client
+                .translate_text()
+                .source_language_code(ctx.target_language_code)
+                .target_language_code(ctx.source_language_code)
+                .text(trans_s)
+                .send()
+                .await?;
+
+            let back_translated = response.translated_text;
+            let similarity = similarity(orig_s, &back_translated);
+
+            if similarity < ctx.threshold {
+                findings.push(Finding {
+                    key: path,
+                    original: orig_s.clone(),
+                    forward: trans_s.clone(),
+                    back_translated,
+                    similarity,
+                });
+            }
+        }
+        // Non-string leaves and shape mismatches carry nothing to verify
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Normalized similarity between two strings in `[0.0, 1.0]`, based on
+/// Levenshtein edit distance relative to the longer string's length.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let distance = levenshtein(&a, &b);
+    let max_len = a.len().max(b.len());
+    1.0 - (distance as f64 / max_len as f64)
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for (i, &a_char) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let cost = if a_char == b_char { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::similarity;
+
+    #[test]
+    fn identical_strings_score_one() {
+        assert_eq!(similarity("hello world", "hello world"), 1.0);
+    }
+
+    #[test]
+    fn completely_different_strings_score_low() {
+        assert!(similarity("hello", "xyzzy") < 0.3);
+    }
+
+    #[test]
+    fn empty_strings_score_one() {
+        assert_eq!(similarity("", ""), 1.0);
+    }
+}